@@ -1,5 +1,8 @@
 use criterion::{black_box, criterion_group, Criterion};
-use nautilus_model::types::fixed::{f64_to_fixed_i64, fixed_i64_to_f64};
+use nautilus_model::types::fixed::{
+    f64_to_fixed_i128, f64_to_fixed_i64, f64_to_fixed_i64_with, fixed_i128_to_f64,
+    fixed_i64_to_f64, RoundingMode,
+};
 
 // #[case(-1.0, 1)]
 pub fn criterion_fixed_precision_benchmark(c: &mut Criterion) {
@@ -7,6 +10,30 @@ pub fn criterion_fixed_precision_benchmark(c: &mut Criterion) {
         // b.iter(|| f64_to_fixed_i64(black_box(-0.000000001), black_box(9)))
         b.iter(|| f64_to_fixed_i64(black_box(-1.0), black_box(1)))
     });
+
+    for mode in [
+        RoundingMode::HalfUp,
+        RoundingMode::HalfEven,
+        RoundingMode::TowardZero,
+        RoundingMode::Ceil,
+        RoundingMode::Floor,
+    ] {
+        c.bench_function(&format!("f64_to_fixed_i64_with/{mode:?}"), |b| {
+            b.iter(|| f64_to_fixed_i64_with(black_box(-1.5), black_box(1), black_box(mode)))
+        });
+    }
+
+    c.bench_function("fixed_i64_to_f64", |b| {
+        b.iter(|| fixed_i64_to_f64(black_box(-1_000_000_000)))
+    });
+
+    // Quantify the cost of the wider backing on platforms without native 128-bit math.
+    c.bench_function("f64_to_fixed_i128", |b| {
+        b.iter(|| f64_to_fixed_i128(black_box(-1.0), black_box(1)))
+    });
+    c.bench_function("fixed_i128_to_f64", |b| {
+        b.iter(|| fixed_i128_to_f64(black_box(-1_000_000_000_000_000_000)))
+    });
 }
 
 criterion_group!(benches, criterion_fixed_precision_benchmark);
@@ -0,0 +1,539 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+use std::fmt;
+
+pub const FIXED_PRECISION: u8 = 9;
+pub const FIXED_SCALAR: f64 = 1_000_000_000.0; // 10.0**FIXED_PRECISION
+
+pub const FIXED128_PRECISION: u8 = 18;
+pub const FIXED128_SCALAR: f64 = 1_000_000_000_000_000_000.0; // 10.0**FIXED128_PRECISION
+
+/// Represents an error converting a value to or from the fixed-point representation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FixedError {
+    /// The `precision` exceeded the applicable maximum (`max`).
+    PrecisionExceeded { precision: u8, max: u8 },
+    /// The input string was not a valid decimal number.
+    InvalidString { value: String },
+    /// The parsed value did not fit within the fixed-point range.
+    OutOfRange { value: String },
+    /// A negative value was supplied where a non-negative one is required.
+    NegativeValue { value: String },
+    /// A checked arithmetic operation underflowed or overflowed.
+    Arithmetic,
+}
+
+impl fmt::Display for FixedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PrecisionExceeded { precision, max } => {
+                write!(f, "precision {precision} exceeded maximum {max}")
+            }
+            Self::InvalidString { value } => write!(f, "invalid decimal string '{value}'"),
+            Self::OutOfRange { value } => {
+                write!(f, "value '{value}' out of range for fixed-point")
+            }
+            Self::NegativeValue { value } => {
+                write!(f, "negative value '{value}' not permitted")
+            }
+            Self::Arithmetic => write!(f, "checked arithmetic over/underflow"),
+        }
+    }
+}
+
+impl std::error::Error for FixedError {}
+
+/// The rounding rule applied when quantizing a value to the fixed representation.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Round half away from zero (the default, matching historical behavior).
+    #[default]
+    HalfUp,
+    /// Round half to the nearest even digit (banker's rounding), eliminating cumulative drift.
+    HalfEven,
+    /// Truncate toward zero.
+    TowardZero,
+    /// Round toward positive infinity.
+    Ceil,
+    /// Round toward negative infinity.
+    Floor,
+}
+
+/// Rounds `value` to the nearest integer according to `mode`.
+fn round_f64(value: f64, mode: RoundingMode) -> f64 {
+    match mode {
+        RoundingMode::HalfUp => value.round(),
+        RoundingMode::HalfEven => {
+            let floor = value.floor();
+            let diff = value - floor;
+            if diff < 0.5 {
+                floor
+            } else if diff > 0.5 {
+                floor + 1.0
+            } else if floor.rem_euclid(2.0) == 0.0 {
+                floor
+            } else {
+                floor + 1.0
+            }
+        }
+        RoundingMode::TowardZero => value.trunc(),
+        RoundingMode::Ceil => value.ceil(),
+        RoundingMode::Floor => value.floor(),
+    }
+}
+
+/// Checks the given `precision` is within the maximum `FIXED_PRECISION`.
+fn check_fixed_precision(precision: u8) -> Result<(), FixedError> {
+    if precision > FIXED_PRECISION {
+        return Err(FixedError::PrecisionExceeded {
+            precision,
+            max: FIXED_PRECISION,
+        });
+    }
+    Ok(())
+}
+
+/// Checks the given `precision` is within the maximum `FIXED128_PRECISION`.
+fn check_fixed128_precision(precision: u8) -> Result<(), FixedError> {
+    if precision > FIXED128_PRECISION {
+        return Err(FixedError::PrecisionExceeded {
+            precision,
+            max: FIXED128_PRECISION,
+        });
+    }
+    Ok(())
+}
+
+#[must_use]
+pub fn f64_to_fixed_i64(value: f64, precision: u8) -> i64 {
+    f64_to_fixed_i64_with(value, precision, RoundingMode::HalfUp)
+}
+
+/// Converts `value` to a `fixed_i64` at the given `precision` using the supplied rounding `mode`.
+///
+/// [`f64_to_fixed_i64`] is the default wrapper applying [`RoundingMode::HalfUp`]. Use
+/// [`RoundingMode::HalfEven`] for financial aggregation (VWAP, cumulative notional) where the
+/// accumulated bias of always rounding half up is material.
+#[must_use]
+pub fn f64_to_fixed_i64_with(value: f64, precision: u8, mode: RoundingMode) -> i64 {
+    assert!(precision <= FIXED_PRECISION, "precision exceeded maximum 9");
+    let pow1 = 10_i64.pow(u32::from(precision));
+    let pow2 = 10_i64.pow(u32::from(FIXED_PRECISION - precision));
+    let rounded = round_f64(value * pow1 as f64, mode) as i64;
+    rounded * pow2
+}
+
+#[must_use]
+pub fn fixed_i64_to_f64(value: i64) -> f64 {
+    value as f64 / FIXED_SCALAR
+}
+
+#[must_use]
+pub fn f64_to_fixed_u64(value: f64, precision: u8) -> u64 {
+    f64_to_fixed_u64_with(value, precision, RoundingMode::HalfUp)
+}
+
+/// Converts the non-negative `value` to a `fixed_u64` at the given `precision` using `mode`.
+///
+/// The unsigned counterpart to [`f64_to_fixed_i64_with`], giving `Quantity`-like types a full
+/// 64-bit magnitude range. Asserts on a negative `value`, which is semantically invalid for
+/// order sizes and volumes.
+#[must_use]
+pub fn f64_to_fixed_u64_with(value: f64, precision: u8, mode: RoundingMode) -> u64 {
+    assert!(precision <= FIXED_PRECISION, "precision exceeded maximum 9");
+    assert!(value >= 0.0, "negative value not permitted for unsigned fixed-point");
+    let pow1 = 10_u64.pow(u32::from(precision));
+    let pow2 = 10_u64.pow(u32::from(FIXED_PRECISION - precision));
+    let rounded = round_f64(value * pow1 as f64, mode) as u64;
+    rounded * pow2
+}
+
+#[must_use]
+pub fn fixed_u64_to_f64(value: u64) -> f64 {
+    value as f64 / FIXED_SCALAR
+}
+
+/// Parses the decimal string `s` directly into a `fixed_u64` at the given `precision`.
+///
+/// The unsigned counterpart to [`parse_fixed_i64`], rejecting a leading `-` early with
+/// [`FixedError::NegativeValue`] (before any magnitude parsing) so the non-negativity of
+/// quantities and volumes is enforced at the type level. The magnitude is parsed and
+/// bound-checked against `u64::MAX` directly — giving the full 64-bit range rather than
+/// capping at `i64::MAX`.
+pub fn parse_fixed_u64(s: &str, precision: u8) -> Result<u64, FixedError> {
+    check_fixed_precision(precision)?;
+
+    let invalid = || FixedError::InvalidString { value: s.to_string() };
+    let out_of_range = || FixedError::OutOfRange { value: s.to_string() };
+
+    let trimmed = s.trim();
+    if trimmed.is_empty() {
+        return Err(invalid());
+    }
+
+    // Reject negatives before parsing the magnitude.
+    if trimmed.starts_with('-') {
+        return Err(FixedError::NegativeValue { value: s.to_string() });
+    }
+    let body = trimmed.strip_prefix('+').unwrap_or(trimmed);
+
+    let (int_part, frac_part) = match body.split_once('.') {
+        Some((i, f)) => (i, f),
+        None => (body, ""),
+    };
+
+    if (int_part.is_empty() && frac_part.is_empty())
+        || !int_part.bytes().all(|b| b.is_ascii_digit())
+        || !frac_part.bytes().all(|b| b.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+
+    let integer: u128 = if int_part.is_empty() {
+        0
+    } else {
+        int_part.parse().map_err(|_| invalid())?
+    };
+
+    let precision = usize::from(precision);
+    let mut frac: u128 = 0;
+    for &b in frac_part.as_bytes().iter().take(precision) {
+        frac = frac * 10 + u128::from(b - b'0');
+    }
+    for _ in frac_part.len()..precision {
+        frac *= 10;
+    }
+    if let Some(&next) = frac_part.as_bytes().get(precision) {
+        if next >= b'5' {
+            frac += 1;
+        }
+    }
+
+    let scale = 10_u128.pow(u32::from(FIXED_PRECISION));
+    let pow = 10_u128.pow(precision as u32);
+    let magnitude = integer
+        .checked_mul(scale)
+        .and_then(|v| v.checked_add(frac * (scale / pow)))
+        .ok_or_else(out_of_range)?;
+
+    if magnitude > u128::from(u64::MAX) {
+        return Err(out_of_range());
+    }
+
+    Ok(magnitude as u64)
+}
+
+/// Adds two `fixed_u64` values, returning [`FixedError::Arithmetic`] on overflow.
+pub fn checked_add_u64(a: u64, b: u64) -> Result<u64, FixedError> {
+    a.checked_add(b).ok_or(FixedError::Arithmetic)
+}
+
+/// Subtracts `b` from `a`, returning [`FixedError::Arithmetic`] on underflow rather than
+/// silently wrapping.
+pub fn checked_sub_u64(a: u64, b: u64) -> Result<u64, FixedError> {
+    a.checked_sub(b).ok_or(FixedError::Arithmetic)
+}
+
+#[must_use]
+pub fn f64_to_fixed_i128(value: f64, precision: u8) -> i128 {
+    f64_to_fixed_i128_with(value, precision, RoundingMode::HalfUp)
+}
+
+/// Converts `value` to a `fixed_i128` at the given `precision` using the supplied rounding `mode`.
+///
+/// Mirrors [`f64_to_fixed_i64_with`] but retains the wider `i128` backing required to hold both
+/// large notionals and the 18 decimal places common for wei-scale ERC-20 quantities.
+#[must_use]
+pub fn f64_to_fixed_i128_with(value: f64, precision: u8, mode: RoundingMode) -> i128 {
+    assert!(precision <= FIXED128_PRECISION, "precision exceeded maximum 18");
+    let pow1 = 10_i128.pow(u32::from(precision));
+    let pow2 = 10_i128.pow(u32::from(FIXED128_PRECISION - precision));
+    let rounded = round_f64(value * pow1 as f64, mode) as i128;
+    rounded * pow2
+}
+
+#[must_use]
+pub fn fixed_i128_to_f64(value: i128) -> f64 {
+    value as f64 / FIXED128_SCALAR
+}
+
+/// Parses the decimal string `s` directly into a `fixed_i128` at the given `precision`,
+/// without round-tripping through `f64`.
+///
+/// The `i128` counterpart to [`parse_fixed_i64`], supporting precision up to
+/// [`FIXED128_PRECISION`] for high-precision crypto assets.
+pub fn parse_fixed_i128(s: &str, precision: u8) -> Result<i128, FixedError> {
+    check_fixed128_precision(precision)?;
+
+    let invalid = || FixedError::InvalidString { value: s.to_string() };
+    let out_of_range = || FixedError::OutOfRange { value: s.to_string() };
+
+    let trimmed = s.trim();
+    if trimmed.is_empty() {
+        return Err(invalid());
+    }
+
+    let (negative, body) = match trimmed.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+    };
+
+    let (int_part, frac_part) = match body.split_once('.') {
+        Some((i, f)) => (i, f),
+        None => (body, ""),
+    };
+
+    if (int_part.is_empty() && frac_part.is_empty())
+        || !int_part.bytes().all(|b| b.is_ascii_digit())
+        || !frac_part.bytes().all(|b| b.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+
+    let integer: i128 = if int_part.is_empty() {
+        0
+    } else {
+        int_part.parse().map_err(|_| invalid())?
+    };
+
+    let precision = usize::from(precision);
+    let mut frac: i128 = 0;
+    for &b in frac_part.as_bytes().iter().take(precision) {
+        frac = frac * 10 + i128::from(b - b'0');
+    }
+    for _ in frac_part.len()..precision {
+        frac *= 10;
+    }
+    if let Some(&next) = frac_part.as_bytes().get(precision) {
+        if next >= b'5' {
+            frac += 1;
+        }
+    }
+
+    let scale = 10_i128.pow(u32::from(FIXED128_PRECISION));
+    let pow = 10_i128.pow(precision as u32);
+    let magnitude = integer
+        .checked_mul(scale)
+        .and_then(|v| v.checked_add(frac * (scale / pow)))
+        .ok_or_else(out_of_range)?;
+
+    Ok(if negative { -magnitude } else { magnitude })
+}
+
+/// Parses the decimal string `s` directly into a `fixed_i64` at the given `precision`,
+/// without round-tripping through `f64`.
+///
+/// Unlike [`f64_to_fixed_i64`], this preserves the exact decimal value for inputs such
+/// as `"0.000000001"` that cannot be represented in binary floating point. The integer
+/// and fractional parts are parsed independently as base-10, the fraction is padded or
+/// rounded (half away from zero) to `precision` digits, and the result is scaled to the
+/// fixed representation. Returns a [`FixedError`] on an invalid string, precision beyond
+/// `FIXED_PRECISION`, or a value outside the `i64` fixed-point range.
+pub fn parse_fixed_i64(s: &str, precision: u8) -> Result<i64, FixedError> {
+    check_fixed_precision(precision)?;
+
+    let invalid = || FixedError::InvalidString { value: s.to_string() };
+    let out_of_range = || FixedError::OutOfRange { value: s.to_string() };
+
+    let trimmed = s.trim();
+    if trimmed.is_empty() {
+        return Err(invalid());
+    }
+
+    // Preserve the leading sign then parse the magnitude.
+    let (negative, body) = match trimmed.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+    };
+
+    let (int_part, frac_part) = match body.split_once('.') {
+        Some((i, f)) => (i, f),
+        None => (body, ""),
+    };
+
+    // An empty integer part is only valid when a fraction follows (e.g. ".5").
+    if (int_part.is_empty() && frac_part.is_empty())
+        || !int_part.bytes().all(|b| b.is_ascii_digit())
+        || !frac_part.bytes().all(|b| b.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+
+    let integer: i128 = if int_part.is_empty() {
+        0
+    } else {
+        int_part.parse().map_err(|_| invalid())?
+    };
+
+    let precision = usize::from(precision);
+    let mut frac: i128 = 0;
+    for &b in frac_part.as_bytes().iter().take(precision) {
+        frac = frac * 10 + i128::from(b - b'0');
+    }
+    // Pad a short fraction out to `precision` digits.
+    for _ in frac_part.len()..precision {
+        frac *= 10;
+    }
+    // Apply half-away-from-zero rounding on the first truncated digit.
+    if let Some(&next) = frac_part.as_bytes().get(precision) {
+        if next >= b'5' {
+            frac += 1;
+        }
+    }
+
+    let scale = 10_i128.pow(u32::from(FIXED_PRECISION));
+    let pow = 10_i128.pow(precision as u32);
+    let magnitude = integer
+        .checked_mul(scale)
+        .and_then(|v| v.checked_add(frac * (scale / pow)))
+        .ok_or_else(out_of_range)?;
+
+    let signed = if negative { -magnitude } else { magnitude };
+    if signed > i128::from(i64::MAX) || signed < i128::from(i64::MIN) {
+        return Err(out_of_range());
+    }
+
+    Ok(signed as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_f64_to_fixed_i64_round_trip() {
+        let value = f64_to_fixed_i64(1.23, 2);
+        assert_eq!(value, 1_230_000_000);
+        assert_eq!(fixed_i64_to_f64(value), 1.23);
+    }
+
+    #[test]
+    fn test_rounding_modes_at_half() {
+        // 2.5 scaled at precision 0 sits exactly on the half.
+        assert_eq!(f64_to_fixed_i64_with(2.5, 0, RoundingMode::HalfUp), 3_000_000_000);
+        assert_eq!(f64_to_fixed_i64_with(2.5, 0, RoundingMode::HalfEven), 2_000_000_000);
+        assert_eq!(f64_to_fixed_i64_with(3.5, 0, RoundingMode::HalfEven), 4_000_000_000);
+        assert_eq!(f64_to_fixed_i64_with(2.5, 0, RoundingMode::TowardZero), 2_000_000_000);
+        assert_eq!(f64_to_fixed_i64_with(-2.5, 0, RoundingMode::TowardZero), -2_000_000_000);
+        assert_eq!(f64_to_fixed_i64_with(2.1, 0, RoundingMode::Ceil), 3_000_000_000);
+        assert_eq!(f64_to_fixed_i64_with(2.9, 0, RoundingMode::Floor), 2_000_000_000);
+    }
+
+    #[test]
+    fn test_default_wrapper_is_half_up() {
+        assert_eq!(f64_to_fixed_i64(2.5, 0), f64_to_fixed_i64_with(2.5, 0, RoundingMode::HalfUp));
+    }
+
+    #[test]
+    fn test_fixed_u64_round_trip() {
+        let value = f64_to_fixed_u64(1.23, 2);
+        assert_eq!(value, 1_230_000_000);
+        assert_eq!(fixed_u64_to_f64(value), 1.23);
+    }
+
+    #[test]
+    fn test_parse_fixed_u64_rejects_negative() {
+        assert!(matches!(
+            parse_fixed_u64("-0.5", 1),
+            Err(FixedError::NegativeValue { .. })
+        ));
+        assert_eq!(parse_fixed_u64("0.5", 1).unwrap(), 500_000_000);
+    }
+
+    #[test]
+    fn test_parse_fixed_u64_upper_half_range() {
+        // 1e19 exceeds i64::MAX but fits in u64 (~1.84e19); the signed parser would reject it.
+        assert_eq!(parse_fixed_u64("10000000000", 9).unwrap(), 10_000_000_000_000_000_000);
+        // Out-of-i64-range negatives still surface as NegativeValue, not OutOfRange.
+        assert!(matches!(
+            parse_fixed_u64("-10000000000", 9),
+            Err(FixedError::NegativeValue { .. })
+        ));
+    }
+
+    #[test]
+    fn test_checked_sub_u64_underflow() {
+        assert_eq!(checked_sub_u64(3, 1).unwrap(), 2);
+        assert!(matches!(checked_sub_u64(1, 3), Err(FixedError::Arithmetic)));
+        assert!(matches!(checked_add_u64(u64::MAX, 1), Err(FixedError::Arithmetic)));
+    }
+
+    #[test]
+    fn test_fixed_i128_round_trip() {
+        let value = f64_to_fixed_i128(1.23, 2);
+        assert_eq!(value, 1_230_000_000_000_000_000);
+        assert_eq!(fixed_i128_to_f64(value), 1.23);
+    }
+
+    #[test]
+    fn test_parse_fixed_i128_wei_scale() {
+        // 1 wei at 18 decimals is exactly 1 in the fixed representation.
+        assert_eq!(parse_fixed_i128("0.000000000000000001", 18).unwrap(), 1);
+        // A large notional that would overflow the i64 path.
+        assert_eq!(
+            parse_fixed_i128("1000000000.5", 18).unwrap(),
+            1_000_000_000_500_000_000_000_000_000
+        );
+    }
+
+    #[test]
+    fn test_parse_fixed_i128_precision_exceeded() {
+        assert!(matches!(
+            parse_fixed_i128("1.0", 19),
+            Err(FixedError::PrecisionExceeded { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_fixed_i64_exact_small_value() {
+        assert_eq!(parse_fixed_i64("0.000000001", 9).unwrap(), 1);
+        assert_eq!(parse_fixed_i64("-0.000000001", 9).unwrap(), -1);
+    }
+
+    #[test]
+    fn test_parse_fixed_i64_matches_float_path() {
+        assert_eq!(parse_fixed_i64("1.23", 2).unwrap(), f64_to_fixed_i64(1.23, 2));
+    }
+
+    #[test]
+    fn test_parse_fixed_i64_rounds_on_truncation() {
+        // Fifth fractional digit rounds the fourth up.
+        assert_eq!(parse_fixed_i64("0.00005", 4).unwrap(), 100_000);
+        assert_eq!(parse_fixed_i64("0.00004", 4).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_parse_fixed_i64_leading_dot_and_sign() {
+        assert_eq!(parse_fixed_i64(".5", 1).unwrap(), 500_000_000);
+        assert_eq!(parse_fixed_i64("+12", 0).unwrap(), 12_000_000_000);
+    }
+
+    #[test]
+    fn test_parse_fixed_i64_invalid_inputs() {
+        assert!(matches!(
+            parse_fixed_i64("abc", 2),
+            Err(FixedError::InvalidString { .. })
+        ));
+        assert!(matches!(
+            parse_fixed_i64("1.0", 10),
+            Err(FixedError::PrecisionExceeded { .. })
+        ));
+        assert!(matches!(
+            parse_fixed_i64("10000000000", 0),
+            Err(FixedError::OutOfRange { .. })
+        ));
+    }
+}